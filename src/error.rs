@@ -0,0 +1,99 @@
+/*!
+Defines the [IcebergError] returned by fallible operations in this crate.
+*/
+use std::{error::Error, fmt, io};
+
+#[derive(Debug)]
+/// Errors that can occur when working with Iceberg tables.
+pub enum IcebergError {
+    /// A table, namespace, schema or other entity could not be found.
+    NotFound(String),
+    /// An entity that was being created already exists.
+    AlreadyExists(String),
+    /// A commit conflicted with a concurrent change to the table.
+    CommitConflict(String),
+    /// The table metadata is malformed or inconsistent.
+    InvalidMetadata(String),
+    /// The table metadata uses a `format-version` this crate does not support.
+    UnsupportedFormatVersion(u64),
+    /// A schema, type or value does not match the schema.
+    Schema(String),
+    /// An IO operation failed.
+    Io(io::Error),
+    /// Serialising or deserialising JSON failed.
+    Json(serde_json::Error),
+    /// A generic error with a message describing the problem.
+    Message(String),
+}
+
+impl fmt::Display for IcebergError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IcebergError::NotFound(msg) => write!(f, "Not found: {msg}"),
+            IcebergError::AlreadyExists(msg) => write!(f, "Already exists: {msg}"),
+            IcebergError::CommitConflict(msg) => write!(f, "Commit conflict: {msg}"),
+            IcebergError::InvalidMetadata(msg) => write!(f, "Invalid metadata: {msg}"),
+            IcebergError::UnsupportedFormatVersion(version) => {
+                write!(f, "Unsupported format-version {version}")
+            }
+            IcebergError::Schema(msg) => write!(f, "Schema error: {msg}"),
+            IcebergError::Io(err) => write!(f, "IO error: {err}"),
+            IcebergError::Json(err) => write!(f, "JSON error: {err}"),
+            IcebergError::Message(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+impl Error for IcebergError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IcebergError::Io(err) => Some(err),
+            IcebergError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for IcebergError {
+    fn from(err: io::Error) -> Self {
+        IcebergError::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for IcebergError {
+    fn from(err: serde_json::Error) -> Self {
+        IcebergError::Json(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_json_error() {
+        let err: IcebergError = serde_json::from_str::<i32>("not json").unwrap_err().into();
+        assert!(matches!(err, IcebergError::Json(_)));
+        assert!(err.source().is_some());
+        assert!(err.to_string().starts_with("JSON error: "));
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let err: IcebergError = io::Error::new(io::ErrorKind::NotFound, "v1.metadata.json").into();
+        assert!(matches!(err, IcebergError::Io(_)));
+        assert_eq!("IO error: v1.metadata.json", err.to_string());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "Not found: table db.t",
+            IcebergError::NotFound("table db.t".to_string()).to_string()
+        );
+        assert_eq!(
+            "Commit conflict: file deleted",
+            IcebergError::CommitConflict("file deleted".to_string()).to_string()
+        );
+    }
+}
@@ -9,12 +9,14 @@ serialise and deserialise the Iceberg table format.
 
 Currently supported:
 * Parsing table metadata v2.
+* Validating table metadata v2.
 
 Coming soon:
 * Manifest files.
 * Manifest lists.
 * v1 table metadata support.
-* Validation.
 
 */
+pub mod error;
 pub mod model;
+pub mod util;
@@ -5,6 +5,8 @@ serialise and deserialise the JSON.
 ## Table metadata example
 
 ```rust
+use std::str::FromStr;
+
 use iceberg_rs::model::table::TableMetadataV2;
 
 let data = r#"
@@ -58,7 +60,7 @@ let data = r#"
         "default-sort-order-id": 0
     }
 "#;
- let metadata = serde_json::from_str::<TableMetadataV2>(&data).unwrap();
+ let metadata = TableMetadataV2::from_str(data).unwrap();
 ```
 
 */
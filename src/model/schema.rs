@@ -1,6 +1,8 @@
 /*!
 A table’s [schema](https://iceberg.apache.org/spec/#schemas-and-data-types) is a list of named columns, represented by [SchemaV2].
 All data types are either [primitives](PrimitiveType) or nested types, which are [Map], [List], or [Struct]. A table [SchemaV2] is also a [Struct] type.
+
+Default values for fields can be checked against their type with [validate_default].
 */
 use lazy_static::lazy_static;
 use regex::Regex;
@@ -8,6 +10,9 @@ use serde::{
     de::{self, IntoDeserializer},
     Deserialize, Deserializer, Serialize,
 };
+use serde_json::{json, Value as JsonValue};
+
+use crate::error::IcebergError;
 
 #[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -160,6 +165,43 @@ pub enum AllType {
     Map(Map),
 }
 
+impl AllType {
+    /// Compare two types ignoring field docs and the order of struct fields.
+    /// See [Struct::is_compatible_with].
+    pub fn is_compatible_with(&self, other: &AllType) -> bool {
+        match (self, other) {
+            (AllType::Primitive(p), AllType::Primitive(o)) => p == o,
+            (AllType::Struct(s), AllType::Struct(o)) => s.is_compatible_with(o),
+            (AllType::List(l), AllType::List(o)) => {
+                l.element_id == o.element_id
+                    && l.element_required == o.element_required
+                    && l.element.is_compatible_with(&o.element)
+            }
+            (AllType::Map(m), AllType::Map(o)) => {
+                m.key_id == o.key_id
+                    && m.value_id == o.value_id
+                    && m.value_required == o.value_required
+                    && m.key.is_compatible_with(&o.key)
+                    && m.value.is_compatible_with(&o.value)
+            }
+            _ => false,
+        }
+    }
+
+    /// Check the defaults of any fields nested in this type with [validate_default].
+    pub fn validate_defaults(&self) -> Result<(), IcebergError> {
+        match self {
+            AllType::Primitive(_) => Ok(()),
+            AllType::Struct(s) => s.validate_defaults(),
+            AllType::List(l) => l.element.validate_defaults(),
+            AllType::Map(m) => {
+                m.key.validate_defaults()?;
+                m.value.validate_defaults()
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "type")]
 /// A struct is a tuple of typed values. Each field in the tuple is
@@ -172,6 +214,41 @@ pub struct Struct {
     pub fields: Vec<StructField>,
 }
 
+impl Struct {
+    /// Compare two structs by field ids, names, types and nullability, ignoring
+    /// field docs and the order the fields are declared in.
+    /// Unlike `==` this is applied recursively to nested types.
+    pub fn is_compatible_with(&self, other: &Struct) -> bool {
+        self.fields.len() == other.fields.len()
+            && self.fields.iter().all(|field| {
+                other
+                    .fields
+                    .iter()
+                    .find(|other_field| other_field.id == field.id)
+                    .map(|other_field| {
+                        field.name == other_field.name
+                            && field.required == other_field.required
+                            && field.field_type.is_compatible_with(&other_field.field_type)
+                    })
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Check the `initial-default` and `write-default` of every field, including
+    /// fields of nested types, with [validate_default].
+    pub fn validate_defaults(&self) -> Result<(), IcebergError> {
+        self.fields.iter().try_for_each(|field| {
+            for default in [&field.initial_default, &field.write_default]
+                .into_iter()
+                .flatten()
+            {
+                validate_default(default, &field.field_type)?;
+            }
+            field.field_type.validate_defaults()
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 /// Details of a struct in a field.
 pub struct StructField {
@@ -185,6 +262,14 @@ pub struct StructField {
     pub field_type: AllType,
     /// Fields can have any optional comment or doc string.
     pub doc: Option<String>,
+    /// Default value for the field when reading rows written before the field was added,
+    /// stored as [single-value JSON](https://iceberg.apache.org/spec/#json-single-value-serialization).
+    #[serde(rename = "initial-default")]
+    pub initial_default: Option<JsonValue>,
+    /// Default value for the field when a writer does not supply one,
+    /// stored as [single-value JSON](https://iceberg.apache.org/spec/#json-single-value-serialization).
+    #[serde(rename = "write-default")]
+    pub write_default: Option<JsonValue>,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -204,6 +289,74 @@ pub struct SchemaV2 {
     pub struct_fields: Struct,
 }
 
+impl SchemaV2 {
+    /// The schema as the JSON described by the
+    /// [spec](https://iceberg.apache.org/spec/#schemas), with each field's type
+    /// under `type` and nested lists and maps carrying their element, key and value ids.
+    pub fn to_canonical_json(&self) -> JsonValue {
+        let mut schema = canonical_struct_json(&self.struct_fields);
+        if let JsonValue::Object(fields) = &mut schema {
+            fields.insert("schema-id".to_string(), json!(self.schema_id));
+            if let Some(ids) = &self.identifier_field_ids {
+                fields.insert("identifier-field-ids".to_string(), json!(ids));
+            }
+        }
+        schema
+    }
+}
+
+/// Canonical JSON for a [Struct] type.
+fn canonical_struct_json(struct_type: &Struct) -> JsonValue {
+    let fields: Vec<JsonValue> = struct_type
+        .fields
+        .iter()
+        .map(|field| {
+            let mut json = json!({
+                "id": field.id,
+                "name": field.name,
+                "required": field.required,
+                "type": canonical_type_json(&field.field_type),
+            });
+            if let JsonValue::Object(map) = &mut json {
+                if let Some(doc) = &field.doc {
+                    map.insert("doc".to_string(), json!(doc));
+                }
+                if let Some(initial_default) = &field.initial_default {
+                    map.insert("initial-default".to_string(), initial_default.clone());
+                }
+                if let Some(write_default) = &field.write_default {
+                    map.insert("write-default".to_string(), write_default.clone());
+                }
+            }
+            json
+        })
+        .collect();
+    json!({ "type": "struct", "fields": fields })
+}
+
+/// Canonical JSON for any [AllType].
+fn canonical_type_json(field_type: &AllType) -> JsonValue {
+    match field_type {
+        AllType::Primitive(PrimitiveType::Timestampz) => json!("timestamptz"),
+        AllType::Primitive(primitive) => json!(primitive),
+        AllType::Struct(struct_type) => canonical_struct_json(struct_type),
+        AllType::List(list) => json!({
+            "type": "list",
+            "element-id": list.element_id,
+            "element-required": list.element_required,
+            "element": canonical_type_json(&list.element),
+        }),
+        AllType::Map(map) => json!({
+            "type": "map",
+            "key-id": map.key_id,
+            "key": canonical_type_json(&map.key),
+            "value-id": map.value_id,
+            "value-required": map.value_required,
+            "value": canonical_type_json(&map.value),
+        }),
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case", tag = "list")]
 /// A Schema type that contains List  elements.
@@ -259,6 +412,168 @@ pub struct NameMapping {
     pub fields: Option<Vec<NameMapping>>,
 }
 
+/// Check that a default value is a valid
+/// [single-value JSON](https://iceberg.apache.org/spec/#json-single-value-serialization)
+/// serialization for the given type.
+pub fn validate_default(value: &JsonValue, ty: &AllType) -> Result<(), IcebergError> {
+    match (ty, value) {
+        (_, JsonValue::Null) => Ok(()),
+        (AllType::Primitive(primitive), _) => validate_primitive_default(value, primitive),
+        (AllType::Struct(Struct { fields }), JsonValue::Object(values)) => fields
+            .iter()
+            .try_for_each(|field| match values.get(&field.id.to_string()) {
+                None | Some(JsonValue::Null) if field.required => Err(invalid_default(
+                    value,
+                    &format!("required field {} is missing", field.id),
+                )),
+                None => Ok(()),
+                Some(field_value) => validate_default(field_value, &field.field_type),
+            }),
+        (
+            AllType::List(List {
+                element,
+                element_required,
+                ..
+            }),
+            JsonValue::Array(elements),
+        ) => elements.iter().try_for_each(|element_value| {
+            validate_nested_default(element_value, element, *element_required, "list element")
+        }),
+        (
+            AllType::Map(Map {
+                key,
+                value: v,
+                value_required,
+                ..
+            }),
+            JsonValue::Object(map),
+        ) => match (map.get("keys"), map.get("values")) {
+            (Some(JsonValue::Array(keys)), Some(JsonValue::Array(values)))
+                if keys.len() == values.len() =>
+            {
+                keys.iter().try_for_each(|key_value| {
+                    validate_nested_default(key_value, key, true, "map key")
+                })?;
+                values.iter().try_for_each(|value_value| {
+                    validate_nested_default(value_value, v, *value_required, "map value")
+                })
+            }
+            _ => Err(invalid_default(
+                value,
+                "map must have keys and values arrays of the same length",
+            )),
+        },
+        _ => Err(invalid_default(value, "value does not match the type")),
+    }
+}
+
+/// Check a list element, map key or map value, rejecting `null` when it is required.
+fn validate_nested_default(
+    value: &JsonValue,
+    ty: &AllType,
+    required: bool,
+    kind: &str,
+) -> Result<(), IcebergError> {
+    if required && value.is_null() {
+        Err(invalid_default(value, &format!("required {kind} is null")))
+    } else {
+        validate_default(value, ty)
+    }
+}
+
+/// Check a default value for a [PrimitiveType].
+fn validate_primitive_default(
+    value: &JsonValue,
+    primitive: &PrimitiveType,
+) -> Result<(), IcebergError> {
+    lazy_static! {
+        static ref DATE: Regex = Regex::new(r#"^(?P<y>\d{4})-(?P<m>\d{2})-(?P<d>\d{2})$"#).unwrap();
+        static ref TIME: Regex = Regex::new(r#"^([01]\d|2[0-3]):[0-5]\d:[0-5]\d(\.\d{1,6})?$"#).unwrap();
+        static ref TIMESTAMP: Regex = Regex::new(
+            r#"^(?P<y>\d{4})-(?P<m>\d{2})-(?P<d>\d{2})T([01]\d|2[0-3]):[0-5]\d:[0-5]\d(\.\d{1,6})?$"#
+        )
+        .unwrap();
+        static ref TIMESTAMPZ: Regex = Regex::new(
+            r#"^(?P<y>\d{4})-(?P<m>\d{2})-(?P<d>\d{2})T([01]\d|2[0-3]):[0-5]\d:[0-5]\d(\.\d{1,6})?[+-]\d{2}:\d{2}$"#
+        )
+        .unwrap();
+        static ref UUID: Regex = Regex::new(
+            r#"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"#
+        )
+        .unwrap();
+        static ref HEX: Regex = Regex::new(r#"^([0-9a-fA-F]{2})*$"#).unwrap();
+        static ref DECIMAL: Regex = Regex::new(r#"^-?(?P<i>\d+)(\.(?P<f>\d+))?$"#).unwrap();
+    }
+
+    let valid = match (primitive, value) {
+        (PrimitiveType::Boolean, JsonValue::Bool(_)) => true,
+        (PrimitiveType::Int, JsonValue::Number(n)) => n
+            .as_i64()
+            .map(|i| i32::try_from(i).is_ok())
+            .unwrap_or(false),
+        (PrimitiveType::Long, JsonValue::Number(n)) => n.is_i64(),
+        (PrimitiveType::Float | PrimitiveType::Double, JsonValue::Number(_)) => true,
+        (PrimitiveType::Decimal { precision, scale }, JsonValue::String(s)) => DECIMAL
+            .captures(s)
+            .map(|caps| {
+                // leading zeros are not significant, so "0.50" has two digits
+                let integer = caps
+                    .name("i")
+                    .map(|i| i.as_str().trim_start_matches('0').len())
+                    .unwrap_or(0);
+                let fraction = caps.name("f").map(|f| f.as_str().len()).unwrap_or(0);
+                fraction == *scale as usize && (integer + fraction) as i32 <= *precision
+            })
+            .unwrap_or(false),
+        (PrimitiveType::Date, JsonValue::String(s)) => is_calendar_date(&DATE, s),
+        (PrimitiveType::Time, JsonValue::String(s)) => TIME.is_match(s),
+        (PrimitiveType::Timestamp, JsonValue::String(s)) => is_calendar_date(&TIMESTAMP, s),
+        (PrimitiveType::Timestampz, JsonValue::String(s)) => is_calendar_date(&TIMESTAMPZ, s),
+        (PrimitiveType::String, JsonValue::String(_)) => true,
+        (PrimitiveType::Uuid, JsonValue::String(s)) => UUID.is_match(s),
+        (PrimitiveType::Fixed(length), JsonValue::String(s)) => {
+            HEX.is_match(s) && s.len() as u64 == length * 2
+        }
+        (PrimitiveType::Binary, JsonValue::String(s)) => HEX.is_match(s),
+        _ => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_default(
+            value,
+            &format!("not a valid {}", serde_json::json!(primitive)),
+        ))
+    }
+}
+
+/// Check `s` matches `re` and its `y`, `m` and `d` captures form a real calendar date.
+fn is_calendar_date(re: &Regex, s: &str) -> bool {
+    let caps = match re.captures(s) {
+        Some(caps) => caps,
+        None => return false,
+    };
+    let part = |name| caps.name(name).and_then(|p| p.as_str().parse::<u32>().ok());
+    match (part("y"), part("m"), part("d")) {
+        (Some(year), Some(month), Some(day)) => {
+            let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+            let days_in_month = match month {
+                1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+                4 | 6 | 9 | 11 => 30,
+                2 if leap => 29,
+                2 => 28,
+                _ => return false,
+            };
+            (1..=days_in_month).contains(&day)
+        }
+        _ => false,
+    }
+}
+
+fn invalid_default(value: &JsonValue, reason: &str) -> IcebergError {
+    IcebergError::Schema(format!("Invalid default {value}: {reason}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,7 +585,7 @@ mod tests {
             "fields": []
         }
         "#;
-        assert!(serde_json::from_str::<Struct>(&data).is_ok());
+        assert!(serde_json::from_str::<Struct>(data).is_ok());
         let data = r#"
         {
             "type" : "anyother"            
@@ -382,6 +697,8 @@ mod tests {
                 required: true,
                 field_type: AllType::Primitive(primitive.clone()),
                 doc: None,
+                initial_default: None,
+                write_default: None,
             };
 
             let j = serde_json::to_string(&sf).unwrap();
@@ -480,4 +797,356 @@ mod tests {
         assert_eq!(Some(3), name_mapping.field_id);
         assert!(name_mapping.fields.is_some())
     }
+
+    #[test]
+    fn test_validate_default() {
+        let valid = vec![
+            (json!(true), PrimitiveType::Boolean),
+            (json!(42), PrimitiveType::Int),
+            (json!(3_000_000_000_i64), PrimitiveType::Long),
+            (json!(1.5), PrimitiveType::Double),
+            (
+                json!("14.20"),
+                PrimitiveType::Decimal {
+                    precision: 9,
+                    scale: 2,
+                },
+            ),
+            (
+                json!("0.50"),
+                PrimitiveType::Decimal {
+                    precision: 2,
+                    scale: 2,
+                },
+            ),
+            (json!("2017-11-16"), PrimitiveType::Date),
+            (json!("2016-02-29"), PrimitiveType::Date),
+            (json!("22:31:08.123456"), PrimitiveType::Time),
+            (json!("2017-11-16T22:31:08"), PrimitiveType::Timestamp),
+            (
+                json!("2017-11-16T22:31:08.123+00:00"),
+                PrimitiveType::Timestampz,
+            ),
+            (json!("iceberg"), PrimitiveType::String),
+            (
+                json!("f79c3e09-677c-4bbd-a479-3f349cb785e7"),
+                PrimitiveType::Uuid,
+            ),
+            (json!("0000FF"), PrimitiveType::Fixed(3)),
+            (json!("0000ff"), PrimitiveType::Binary),
+        ];
+        for (value, primitive) in valid {
+            assert!(
+                validate_default(&value, &AllType::Primitive(primitive.clone())).is_ok(),
+                "{value} should be a valid {primitive:?}"
+            );
+        }
+
+        let invalid = vec![
+            (json!("true"), PrimitiveType::Boolean),
+            (json!(3_000_000_000_i64), PrimitiveType::Int),
+            (json!(1.5), PrimitiveType::Long),
+            (
+                json!("14.2"),
+                PrimitiveType::Decimal {
+                    precision: 9,
+                    scale: 2,
+                },
+            ),
+            (json!("2017-13-16"), PrimitiveType::Date),
+            (json!("2017-02-29"), PrimitiveType::Date),
+            (json!("2017-04-31T22:31:08"), PrimitiveType::Timestamp),
+            (
+                json!("2017-02-30T22:31:08+00:00"),
+                PrimitiveType::Timestampz,
+            ),
+            (json!("2017-11-16"), PrimitiveType::Timestamp),
+            (json!("2017-11-16T22:31:08"), PrimitiveType::Timestampz),
+            (json!(1), PrimitiveType::String),
+            (json!("not-a-uuid"), PrimitiveType::Uuid),
+            (json!("0000FF"), PrimitiveType::Fixed(2)),
+            (json!("AAA="), PrimitiveType::Binary),
+        ];
+        for (value, primitive) in invalid {
+            assert!(
+                validate_default(&value, &AllType::Primitive(primitive.clone())).is_err(),
+                "{value} should not be a valid {primitive:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_nested_default() {
+        let list = AllType::List(List {
+            element_id: 3,
+            element_required: true,
+            element: Box::new(AllType::Primitive(PrimitiveType::Int)),
+        });
+        assert!(validate_default(&json!([1, 2, 3]), &list).is_ok());
+        assert!(validate_default(&json!([1, "2"]), &list).is_err());
+        assert!(validate_default(&json!([1, null]), &list).is_err());
+        let optional_list = AllType::List(List {
+            element_id: 3,
+            element_required: false,
+            element: Box::new(AllType::Primitive(PrimitiveType::Int)),
+        });
+        assert!(validate_default(&json!([1, null]), &optional_list).is_ok());
+
+        let map = AllType::Map(Map {
+            key_id: 4,
+            key: Box::new(AllType::Primitive(PrimitiveType::String)),
+            value_id: 5,
+            value_required: false,
+            value: Box::new(AllType::Primitive(PrimitiveType::Double)),
+        });
+        assert!(
+            validate_default(&json!({"keys": ["a", "b"], "values": [1.0, null]}), &map).is_ok()
+        );
+        assert!(validate_default(&json!({"keys": ["a"], "values": []}), &map).is_err());
+        assert!(validate_default(&json!({"keys": [null], "values": [1.0]}), &map).is_err());
+        let required_map = AllType::Map(Map {
+            key_id: 4,
+            key: Box::new(AllType::Primitive(PrimitiveType::String)),
+            value_id: 5,
+            value_required: true,
+            value: Box::new(AllType::Primitive(PrimitiveType::Double)),
+        });
+        assert!(
+            validate_default(&json!({"keys": ["a"], "values": [null]}), &required_map).is_err()
+        );
+        assert!(validate_default(&json!({"keys": ["a"], "values": [1.0]}), &required_map).is_ok());
+
+        let struct_type = AllType::Struct(Struct {
+            fields: vec![
+                StructField {
+                    id: 1,
+                    name: "id".to_string(),
+                    required: true,
+                    field_type: AllType::Primitive(PrimitiveType::Long),
+                    doc: None,
+                    initial_default: None,
+                    write_default: None,
+                },
+                StructField {
+                    id: 2,
+                    name: "data".to_string(),
+                    required: false,
+                    field_type: AllType::Primitive(PrimitiveType::String),
+                    doc: None,
+                    initial_default: None,
+                    write_default: None,
+                },
+            ],
+        });
+        assert!(validate_default(&json!({"1": 1}), &struct_type).is_ok());
+        assert!(matches!(
+            validate_default(&json!({"2": "a"}), &struct_type),
+            Err(IcebergError::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn test_struct_compatibility() {
+        let field = |id: i32, name: &str, field_type: AllType, doc: Option<&str>| StructField {
+            id,
+            name: name.to_string(),
+            required: true,
+            field_type,
+            doc: doc.map(str::to_string),
+            initial_default: None,
+            write_default: None,
+        };
+        let nested = |doc: Option<&str>| {
+            AllType::List(List {
+                element_id: 4,
+                element_required: true,
+                element: Box::new(AllType::Struct(Struct {
+                    fields: vec![field(5, "x", AllType::Primitive(PrimitiveType::Int), doc)],
+                })),
+            })
+        };
+        let schema = Struct {
+            fields: vec![
+                field(1, "id", AllType::Primitive(PrimitiveType::Long), None),
+                field(2, "data", AllType::Primitive(PrimitiveType::String), None),
+                field(3, "points", nested(None), None),
+            ],
+        };
+        let reordered = Struct {
+            fields: vec![
+                field(3, "points", nested(Some("a point")), None),
+                field(1, "id", AllType::Primitive(PrimitiveType::Long), Some("id")),
+                field(2, "data", AllType::Primitive(PrimitiveType::String), None),
+            ],
+        };
+        assert_ne!(schema, reordered);
+        assert!(schema.is_compatible_with(&reordered));
+        assert!(reordered.is_compatible_with(&schema));
+
+        let retyped = Struct {
+            fields: vec![
+                field(1, "id", AllType::Primitive(PrimitiveType::Int), None),
+                field(2, "data", AllType::Primitive(PrimitiveType::String), None),
+                field(3, "points", nested(None), None),
+            ],
+        };
+        assert!(!schema.is_compatible_with(&retyped));
+
+        let mut optional = reordered;
+        optional.fields[2].required = false;
+        assert!(!schema.is_compatible_with(&optional));
+
+        let missing = Struct {
+            fields: vec![field(
+                1,
+                "id",
+                AllType::Primitive(PrimitiveType::Long),
+                None,
+            )],
+        };
+        assert!(!schema.is_compatible_with(&missing));
+    }
+
+    #[test]
+    fn test_canonical_json() {
+        let schema = SchemaV2 {
+            schema_id: 1,
+            identifier_field_ids: Some(vec![1]),
+            name_mapping: None,
+            struct_fields: Struct {
+                fields: vec![
+                    StructField {
+                        id: 1,
+                        name: "id".to_string(),
+                        required: true,
+                        field_type: AllType::Primitive(PrimitiveType::Long),
+                        doc: Some("unique id".to_string()),
+                        initial_default: None,
+                        write_default: None,
+                    },
+                    StructField {
+                        id: 8,
+                        name: "created".to_string(),
+                        required: false,
+                        field_type: AllType::Primitive(PrimitiveType::Timestampz),
+                        doc: None,
+                        initial_default: None,
+                        write_default: None,
+                    },
+                    StructField {
+                        id: 2,
+                        name: "locations".to_string(),
+                        required: false,
+                        field_type: AllType::List(List {
+                            element_id: 3,
+                            element_required: true,
+                            element: Box::new(AllType::Struct(Struct {
+                                fields: vec![
+                                    StructField {
+                                        id: 4,
+                                        name: "lat".to_string(),
+                                        required: true,
+                                        field_type: AllType::Primitive(PrimitiveType::Decimal {
+                                            precision: 9,
+                                            scale: 6,
+                                        }),
+                                        doc: None,
+                                        initial_default: None,
+                                        write_default: None,
+                                    },
+                                    StructField {
+                                        id: 5,
+                                        name: "tags".to_string(),
+                                        required: false,
+                                        field_type: AllType::Map(Map {
+                                            key_id: 6,
+                                            key: Box::new(AllType::Primitive(
+                                                PrimitiveType::String,
+                                            )),
+                                            value_id: 7,
+                                            value_required: false,
+                                            value: Box::new(AllType::Primitive(
+                                                PrimitiveType::Fixed(16),
+                                            )),
+                                        }),
+                                        doc: None,
+                                        initial_default: None,
+                                        write_default: None,
+                                    },
+                                ],
+                            })),
+                        }),
+                        doc: None,
+                        initial_default: None,
+                        write_default: None,
+                    },
+                ],
+            },
+        };
+        let expected: JsonValue = serde_json::from_str(
+            r#"
+            {
+                "type": "struct",
+                "schema-id": 1,
+                "identifier-field-ids": [1],
+                "fields": [
+                    {"id": 1, "name": "id", "required": true, "type": "long", "doc": "unique id"},
+                    {"id": 8, "name": "created", "required": false, "type": "timestamptz"},
+                    {
+                        "id": 2,
+                        "name": "locations",
+                        "required": false,
+                        "type": {
+                            "type": "list",
+                            "element-id": 3,
+                            "element-required": true,
+                            "element": {
+                                "type": "struct",
+                                "fields": [
+                                    {"id": 4, "name": "lat", "required": true, "type": "decimal(9,6)"},
+                                    {
+                                        "id": 5,
+                                        "name": "tags",
+                                        "required": false,
+                                        "type": {
+                                            "type": "map",
+                                            "key-id": 6,
+                                            "key": "string",
+                                            "value-id": 7,
+                                            "value-required": false,
+                                            "value": "fixed[16]"
+                                        }
+                                    }
+                                ]
+                            }
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+        assert_eq!(expected, schema.to_canonical_json());
+    }
+
+    #[test]
+    fn test_field_defaults() {
+        let data = r#"
+        {
+            "id" : 1,
+            "name": "count",
+            "required": true,
+            "field_type": "int",
+            "write-default": 42
+        }
+        "#;
+        let field = serde_json::from_str::<StructField>(data).unwrap();
+        assert_eq!(None, field.initial_default);
+        assert_eq!(Some(json!(42)), field.write_default);
+
+        let json = serde_json::to_value(&field).unwrap();
+        assert_eq!(json!(42), json["write-default"]);
+        let unserde: StructField = serde_json::from_value(json).unwrap();
+        assert_eq!(field, unserde);
+    }
 }
@@ -2,6 +2,7 @@
 Details of [snapshots](https://iceberg.apache.org/spec/#snapshots) for a table.
 
 A [SnapshotV2] contains a pointer to the ManifestList as well as supporting data for the Snapshot.
+The counts recorded in its [Summary] are available through typed accessors such as [SnapshotV2::total_records].
 
 A [Reference] is a named pointer to a [SnapshotV2] stored in the [refs field of the TableMetadataV2](crate::model::table::TableMetadataV2#structfield.refs).
 a [Reference] can be a [Tag](Retention#variant.Tag) or [Branch](Retention#variant.Branch).
@@ -64,6 +65,74 @@ pub struct SnapshotV2 {
     pub schema_id: Option<i64>,
 }
 
+impl SnapshotV2 {
+    /// Number of data files added in the snapshot.
+    pub fn added_data_files(&self) -> Option<i64> {
+        self.summary_value("added-data-files")
+    }
+
+    /// Number of data files deleted in the snapshot.
+    pub fn deleted_data_files(&self) -> Option<i64> {
+        self.summary_value("deleted-data-files")
+    }
+
+    /// Total number of live data files in the snapshot.
+    pub fn total_data_files(&self) -> Option<i64> {
+        self.summary_value("total-data-files")
+    }
+
+    /// Number of positional and equality delete files added in the snapshot.
+    pub fn added_delete_files(&self) -> Option<i64> {
+        self.summary_value("added-delete-files")
+    }
+
+    /// Number of positional and equality delete files removed in the snapshot.
+    pub fn removed_delete_files(&self) -> Option<i64> {
+        self.summary_value("removed-delete-files")
+    }
+
+    /// Total number of live positional and equality delete files in the snapshot.
+    pub fn total_delete_files(&self) -> Option<i64> {
+        self.summary_value("total-delete-files")
+    }
+
+    /// Number of records added in the snapshot.
+    pub fn added_records(&self) -> Option<i64> {
+        self.summary_value("added-records")
+    }
+
+    /// Number of records deleted in the snapshot.
+    pub fn deleted_records(&self) -> Option<i64> {
+        self.summary_value("deleted-records")
+    }
+
+    /// Total number of records in the snapshot.
+    pub fn total_records(&self) -> Option<i64> {
+        self.summary_value("total-records")
+    }
+
+    /// Total size in bytes of the files added in the snapshot.
+    pub fn added_files_size(&self) -> Option<i64> {
+        self.summary_value("added-files-size")
+    }
+
+    /// Total size in bytes of the files removed in the snapshot.
+    pub fn removed_files_size(&self) -> Option<i64> {
+        self.summary_value("removed-files-size")
+    }
+
+    /// Total size in bytes of the live files in the snapshot.
+    pub fn total_files_size(&self) -> Option<i64> {
+        self.summary_value("total-files-size")
+    }
+
+    /// Parse a numeric value from the snapshot summary, `None` if it is
+    /// missing or not a valid number.
+    fn summary_value(&self, key: &str) -> Option<i64> {
+        self.summary.other.get(key).and_then(|v| v.parse().ok())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 /// Iceberg tables keep track of branches and tags using snapshot references.
@@ -119,7 +188,7 @@ mod tests {
             } 
         "#;
 
-        let snapshot: SnapshotV2 = serde_json::from_str(&data).unwrap();
+        let snapshot: SnapshotV2 = serde_json::from_str(data).unwrap();
         assert_eq!(Some(Operation::Append), snapshot.summary.operation);
         assert!(snapshot.summary.other.is_empty());
     }
@@ -171,4 +240,35 @@ mod tests {
         let result: Retention = serde_json::from_str(&json).unwrap();
         assert!(matches!(result, Retention::Tag { .. }))
     }
+
+    #[test]
+    fn test_snapshot_summary_counts() {
+        let data = r#"
+            {
+                "snapshot-id": 3051729675574597004,
+                "sequence-number": 1,
+                "timestamp-ms": 1515100955770,
+                "summary": {
+                    "operation": "append",
+                    "added-data-files": "2",
+                    "added-records": "100",
+                    "added-files-size": "2048",
+                    "total-data-files": "4",
+                    "total-records": "250",
+                    "total-files-size": "not-a-number"
+                },
+                "manifest-list": "s3://b/wh/.../s1.avro",
+                "schema-id": 0
+            }
+        "#;
+
+        let snapshot: SnapshotV2 = serde_json::from_str(data).unwrap();
+        assert_eq!(Some(2), snapshot.added_data_files());
+        assert_eq!(Some(100), snapshot.added_records());
+        assert_eq!(Some(2048), snapshot.added_files_size());
+        assert_eq!(Some(4), snapshot.total_data_files());
+        assert_eq!(Some(250), snapshot.total_records());
+        assert_eq!(None, snapshot.total_files_size());
+        assert_eq!(None, snapshot.deleted_records());
+    }
 }
@@ -1,23 +1,32 @@
 /*!
 Defines the [table metadata](https://iceberg.apache.org/spec/#table-metadata).
 The main struct here is [TableMetadataV2] which defines the data for a table.
+
+Table metadata is parsed with [TableMetadataV2::from_str], which reports an
+unsupported `format-version` as [IcebergError::UnsupportedFormatVersion].
+
+The property keys reserved by Iceberg are available as [ReservedProperty].
+
+Metadata that bends the spec can be parsed with [TableMetadataV2::from_str_with]
+using a lenient [MetadataParseOptions].
 */
-use std::collections::HashMap;
+use std::{collections::HashMap, str::FromStr};
 
+use crate::error::IcebergError;
 use crate::model::{
     partition::PartitionSpec,
     schema,
-    snapshot::{Reference, SnapshotV2},
+    snapshot::{Reference, Retention, SnapshotV2},
     sort,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value as JsonValue;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "kebab-case", tag = "format-version")]
+#[serde(rename_all = "kebab-case", remote = "Self")]
 /// Fields for the version 2 of the table metadata.
 pub struct TableMetadataV2 {
-    /// Integer Version for the format.
     /// A UUID that identifies the table
     pub table_uuid: Uuid,
     /// Location tables base location
@@ -79,6 +88,254 @@ pub struct TableMetadataV2 {
     pub refs: Option<HashMap<String, Reference>>,
 }
 
+/// The name of the branch that tracks the current snapshot of a table.
+pub const MAIN_BRANCH: &str = "main";
+
+/// The `format-version` written for and accepted by [TableMetadataV2].
+const FORMAT_VERSION_V2: u64 = 2;
+
+/// Error for the `-1` `current-snapshot-id` some writers use to mean no snapshot.
+const NO_SNAPSHOT_SENTINEL_MESSAGE: &str =
+    "current-snapshot-id -1 must be omitted when the table has no snapshot";
+
+/// Serialize for TableMetadataV2 adding the `format-version` field.
+impl Serialize for TableMetadataV2 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        #[derive(Serialize)]
+        struct Versioned<'a> {
+            #[serde(rename = "format-version")]
+            format_version: u64,
+            #[serde(flatten, serialize_with = "TableMetadataV2::serialize")]
+            metadata: &'a TableMetadataV2,
+        }
+        Versioned {
+            format_version: FORMAT_VERSION_V2,
+            metadata: self,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialize for TableMetadataV2 checking the `format-version` field is 2
+/// and `current-snapshot-id` is not `-1`. Failures are plain deserializer errors,
+/// parse with [FromStr] to get an [IcebergError] such as
+/// [IcebergError::UnsupportedFormatVersion].
+impl<'de> Deserialize<'de> for TableMetadataV2 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Versioned {
+            #[serde(rename = "format-version")]
+            format_version: u64,
+            #[serde(flatten, deserialize_with = "TableMetadataV2::deserialize")]
+            metadata: TableMetadataV2,
+        }
+        let versioned = Versioned::deserialize(deserializer)?;
+        if versioned.format_version != FORMAT_VERSION_V2 {
+            Err(de::Error::custom(format!(
+                "Unsupported format-version {}",
+                versioned.format_version
+            )))
+        } else if versioned.metadata.current_snapshot_id == Some(-1) {
+            Err(de::Error::custom(NO_SNAPSHOT_SENTINEL_MESSAGE))
+        } else {
+            Ok(versioned.metadata)
+        }
+    }
+}
+
+impl TableMetadataV2 {
+    /// Parse table metadata from a JSON string with the given options.
+    pub fn from_str_with(s: &str, options: MetadataParseOptions) -> Result<Self, IcebergError> {
+        let mut json: JsonValue = serde_json::from_str(s)?;
+        match json.get("format-version").and_then(JsonValue::as_u64) {
+            Some(FORMAT_VERSION_V2) | None => (),
+            Some(version) => return Err(IcebergError::UnsupportedFormatVersion(version)),
+        }
+        if let JsonValue::Object(fields) = &mut json {
+            let no_snapshot_sentinel =
+                fields.get("current-snapshot-id") == Some(&JsonValue::from(-1));
+            if options.lenient {
+                if no_snapshot_sentinel {
+                    fields.insert("current-snapshot-id".to_string(), JsonValue::Null);
+                }
+                fields
+                    .entry("last-sequence-number")
+                    .or_insert_with(|| JsonValue::from(0));
+                fields
+                    .entry("sort-orders")
+                    .or_insert_with(|| JsonValue::Array(vec![]));
+                fields
+                    .entry("default-sort-order-id")
+                    .or_insert_with(|| JsonValue::from(0));
+            } else if no_snapshot_sentinel {
+                return Err(IcebergError::InvalidMetadata(
+                    NO_SNAPSHOT_SENTINEL_MESSAGE.to_string(),
+                ));
+            }
+        }
+        Ok(serde_json::from_value(json)?)
+    }
+
+    /// Get the schema with the given id, including schemas that are no longer current.
+    pub fn schema_by_id(&self, schema_id: i32) -> Option<&schema::SchemaV2> {
+        self.schemas
+            .iter()
+            .find(|schema| schema.schema_id == schema_id)
+    }
+
+    /// Check the ids that reference other parts of the metadata are valid:
+    /// * `current-schema-id` matches a schema.
+    /// * `default-spec-id` matches a partition spec.
+    /// * `default-sort-order-id` matches a sort order, or is `0` for unsorted.
+    /// * `current-snapshot-id`, if set, matches a snapshot.
+    /// * a `main` ref is a branch pointing at `current-snapshot-id`.
+    /// * every ref in `refs` points to a snapshot.
+    /// * every field default in every schema is valid for the field type.
+    pub fn validate(&self) -> Result<(), IcebergError> {
+        self.current_schema()?;
+        for schema in &self.schemas {
+            schema.struct_fields.validate_defaults()?;
+        }
+        self.default_spec()?;
+        if self.default_sort_order_id != 0
+            && !self
+                .sort_orders
+                .iter()
+                .any(|order| order.order_id as i64 == self.default_sort_order_id)
+        {
+            return Err(IcebergError::InvalidMetadata(format!(
+                "default-sort-order-id {} does not match any sort order",
+                self.default_sort_order_id
+            )));
+        }
+        if let Some(snapshot_id) = self.current_snapshot_id {
+            if self.snapshot_by_id(snapshot_id).is_none() {
+                return Err(IcebergError::InvalidMetadata(format!(
+                    "current-snapshot-id {snapshot_id} does not match any snapshot"
+                )));
+            }
+        }
+        if let Some(main) = self.refs.as_ref().and_then(|refs| refs.get(MAIN_BRANCH)) {
+            if !matches!(main.retention, Retention::Branch { .. }) {
+                return Err(IcebergError::InvalidMetadata(format!(
+                    "ref {MAIN_BRANCH} must be a branch"
+                )));
+            }
+            match self.current_snapshot_id {
+                Some(snapshot_id) if snapshot_id == main.snapshot_id => (),
+                Some(snapshot_id) => {
+                    return Err(IcebergError::InvalidMetadata(format!(
+                        "current-snapshot-id {snapshot_id} does not match the {MAIN_BRANCH} branch snapshot {}",
+                        main.snapshot_id
+                    )))
+                }
+                None => {
+                    return Err(IcebergError::InvalidMetadata(format!(
+                        "current-snapshot-id is missing for the {MAIN_BRANCH} branch snapshot {}",
+                        main.snapshot_id
+                    )))
+                }
+            }
+        }
+        for (name, reference) in self.refs.iter().flatten() {
+            if self.snapshot_by_id(reference.snapshot_id).is_none() {
+                return Err(IcebergError::InvalidMetadata(format!(
+                    "ref {name} points to missing snapshot {}",
+                    reference.snapshot_id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the current schema of the table, failing if `current-schema-id`
+    /// does not match any of the table's schemas.
+    pub fn current_schema(&self) -> Result<&schema::SchemaV2, IcebergError> {
+        self.schema_by_id(self.current_schema_id).ok_or_else(|| {
+            IcebergError::InvalidMetadata(format!(
+                "current-schema-id {} does not match any schema",
+                self.current_schema_id
+            ))
+        })
+    }
+
+    /// Get the default partition spec of the table, failing if `default-spec-id`
+    /// does not match any of the table's partition specs.
+    pub fn default_spec(&self) -> Result<&PartitionSpec, IcebergError> {
+        self.partition_specs
+            .iter()
+            .find(|spec| spec.spec_id == self.default_spec_id)
+            .ok_or_else(|| {
+                IcebergError::InvalidMetadata(format!(
+                    "default-spec-id {} does not match any partition spec",
+                    self.default_spec_id
+                ))
+            })
+    }
+
+    /// Get the snapshot with the given id if it is still a valid snapshot.
+    pub fn snapshot_by_id(&self, snapshot_id: i64) -> Option<&SnapshotV2> {
+        self.snapshots
+            .as_ref()?
+            .iter()
+            .find(|snapshot| snapshot.snapshot_id == snapshot_id)
+    }
+
+    /// Get the snapshot a branch or tag in `refs` points to.
+    pub fn snapshot_for_ref(&self, ref_name: &str) -> Option<&SnapshotV2> {
+        self.refs
+            .as_ref()?
+            .get(ref_name)
+            .and_then(|reference| self.snapshot_by_id(reference.snapshot_id))
+    }
+
+    /// Get the current snapshot of the table. The `main` branch in `refs` is
+    /// authoritative, `current-snapshot-id` is used when there is no `main` branch.
+    pub fn current_snapshot(&self) -> Option<&SnapshotV2> {
+        match self.refs.as_ref().and_then(|refs| refs.get(MAIN_BRANCH)) {
+            Some(main) => self.snapshot_by_id(main.snapshot_id),
+            None => self
+                .current_snapshot_id
+                .and_then(|snapshot_id| self.snapshot_by_id(snapshot_id)),
+        }
+    }
+
+    /// Get the value of a reserved property if it is set on the table.
+    pub fn property(&self, property: ReservedProperty) -> Option<&str> {
+        self.properties
+            .as_ref()
+            .and_then(|properties| properties.get(property.as_str()))
+            .map(String::as_str)
+    }
+}
+
+impl FromStr for TableMetadataV2 {
+    type Err = IcebergError;
+
+    /// Parse table metadata from a JSON string, strictly following the spec.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TableMetadataV2::from_str_with(s, MetadataParseOptions::default())
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+/// Options controlling how [TableMetadataV2] is parsed.
+pub struct MetadataParseOptions {
+    /// Tolerate known deviations from the spec found in real-world metadata files:
+    /// * a `current-snapshot-id` of `-1`, used by some writers to mean no snapshot, is read as absent.
+    /// * a missing `last-sequence-number` is read as `0`.
+    /// * missing `sort-orders` and `default-sort-order-id` are read as unsorted.
+    ///
+    /// When `false` the metadata must follow the spec, and each of these is an error.
+    pub lenient: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 /// Encodes changes to the previous metadata files for the table
@@ -99,11 +356,294 @@ pub struct SnapshotLog {
     pub timestamp_ms: i64,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+/// The [table properties](https://iceberg.apache.org/docs/latest/configuration/#table-properties) reserved by Iceberg
+/// to control reading, writing and committing to a table.
+/// Keys that embed a column name, such as `write.metadata.metrics.column.*` and
+/// `write.parquet.bloom-filter-enabled.column.*`, are not included.
+pub enum ReservedProperty {
+    /// Target size when combining data input splits.
+    ReadSplitTargetSize,
+    /// Target size when combining metadata input splits.
+    ReadSplitMetadataTargetSize,
+    /// Number of bins to consider when combining input splits.
+    ReadSplitPlanningLookback,
+    /// The estimated cost to open a file, used as a minimum weight when combining splits.
+    ReadSplitOpenFileCost,
+    /// Controls whether Parquet vectorized reads are used.
+    ReadParquetVectorizationEnabled,
+    /// The batch size for Parquet vectorized reads.
+    ReadParquetVectorizationBatchSize,
+    /// Controls whether ORC vectorized reads are used.
+    ReadOrcVectorizationEnabled,
+    /// The batch size for ORC vectorized reads.
+    ReadOrcVectorizationBatchSize,
+    /// Default file format for the table; parquet, avro, or orc.
+    WriteFormatDefault,
+    /// Default delete file format for the table; parquet, avro, or orc.
+    WriteDeleteFormatDefault,
+    /// Parquet row group size.
+    WriteParquetRowGroupSizeBytes,
+    /// Parquet page size.
+    WriteParquetPageSizeBytes,
+    /// Parquet dictionary page size.
+    WriteParquetDictSizeBytes,
+    /// Parquet compression codec.
+    WriteParquetCompressionCodec,
+    /// Parquet compression level.
+    WriteParquetCompressionLevel,
+    /// The maximum number of bytes for a Parquet bloom filter bitset.
+    WriteParquetBloomFilterMaxBytes,
+    /// Avro compression codec.
+    WriteAvroCompressionCodec,
+    /// Avro compression level.
+    WriteAvroCompressionLevel,
+    /// Define the default ORC stripe size, in bytes.
+    WriteOrcStripeSizeBytes,
+    /// Define the default file system block size for ORC files.
+    WriteOrcBlockSizeBytes,
+    /// ORC compression codec.
+    WriteOrcCompressionCodec,
+    /// ORC compression strategy; speed or compression.
+    WriteOrcCompressionStrategy,
+    /// Comma separated list of column names for which an ORC bloom filter is created.
+    WriteOrcBloomFilterColumns,
+    /// False positive probability for ORC bloom filters.
+    WriteOrcBloomFilterFpp,
+    /// Optional custom implementation for LocationProvider.
+    WriteLocationProviderImpl,
+    /// Metadata compression codec; none or gzip.
+    WriteMetadataCompressionCodec,
+    /// Default metrics mode for all columns in the table.
+    WriteMetadataMetricsDefault,
+    /// Maximum number of columns for which metrics are collected by default.
+    WriteMetadataMetricsMaxInferredColumnDefaults,
+    /// Controls the size of files generated to target about this many bytes.
+    WriteTargetFileSizeBytes,
+    /// Defines distribution of write data; none, hash, or range.
+    WriteDistributionMode,
+    /// Enables write-audit-publish writes.
+    WriteWapEnabled,
+    /// Includes partition-level summary stats in snapshot summaries if the changed partition count is less than this limit.
+    WriteSummaryPartitionLimit,
+    /// Controls whether to delete the oldest tracked version metadata files after commit.
+    WriteMetadataDeleteAfterCommitEnabled,
+    /// The max number of previous version metadata files to keep before deleting after commit.
+    WriteMetadataPreviousVersionsMax,
+    /// Enables the object storage location provider that adds a hash component to file paths.
+    WriteObjectStorageEnabled,
+    /// Base location for data files.
+    WriteDataPath,
+    /// Base location for metadata files.
+    WriteMetadataPath,
+    /// Mode used for delete commands; copy-on-write or merge-on-read.
+    WriteDeleteMode,
+    /// Mode used for update commands; copy-on-write or merge-on-read.
+    WriteUpdateMode,
+    /// Mode used for merge commands; copy-on-write or merge-on-read.
+    WriteMergeMode,
+    /// Number of times to retry a commit before failing.
+    CommitRetryNumRetries,
+    /// Minimum time in milliseconds to wait before retrying a commit.
+    CommitRetryMinWaitMs,
+    /// Maximum time in milliseconds to wait before retrying a commit.
+    CommitRetryMaxWaitMs,
+    /// Maximum time in milliseconds to spend retrying a commit.
+    CommitRetryTotalTimeoutMs,
+    /// Number of times to check whether a commit succeeded after a connection is lost.
+    CommitStatusCheckNumRetries,
+    /// Minimum time in milliseconds to wait before retrying a status-check.
+    CommitStatusCheckMinWaitMs,
+    /// Maximum time in milliseconds to wait before retrying a status-check.
+    CommitStatusCheckMaxWaitMs,
+    /// Maximum time in milliseconds to spend checking the status of a commit.
+    CommitStatusCheckTotalTimeoutMs,
+    /// Target size when merging manifest files.
+    CommitManifestTargetSizeBytes,
+    /// Minimum number of manifests to accumulate before merging.
+    CommitManifestMinCountToMerge,
+    /// Controls whether to automatically merge manifests on writes.
+    CommitManifestMergeEnabled,
+    /// Default max age of snapshots to keep while expiring snapshots.
+    HistoryExpireMaxSnapshotAgeMs,
+    /// Default min number of snapshots to keep while expiring snapshots.
+    HistoryExpireMinSnapshotsToKeep,
+    /// Default max age of snapshot references to keep while expiring snapshots.
+    HistoryExpireMaxRefAgeMs,
+    /// Allows garbage collection of data and metadata files, such as expiring snapshots and removing orphan files.
+    GcEnabled,
+}
+
+impl ReservedProperty {
+    /// Every reserved property.
+    pub const ALL: &'static [ReservedProperty] = &[
+        ReservedProperty::ReadSplitTargetSize,
+        ReservedProperty::ReadSplitMetadataTargetSize,
+        ReservedProperty::ReadSplitPlanningLookback,
+        ReservedProperty::ReadSplitOpenFileCost,
+        ReservedProperty::ReadParquetVectorizationEnabled,
+        ReservedProperty::ReadParquetVectorizationBatchSize,
+        ReservedProperty::ReadOrcVectorizationEnabled,
+        ReservedProperty::ReadOrcVectorizationBatchSize,
+        ReservedProperty::WriteFormatDefault,
+        ReservedProperty::WriteDeleteFormatDefault,
+        ReservedProperty::WriteParquetRowGroupSizeBytes,
+        ReservedProperty::WriteParquetPageSizeBytes,
+        ReservedProperty::WriteParquetDictSizeBytes,
+        ReservedProperty::WriteParquetCompressionCodec,
+        ReservedProperty::WriteParquetCompressionLevel,
+        ReservedProperty::WriteParquetBloomFilterMaxBytes,
+        ReservedProperty::WriteAvroCompressionCodec,
+        ReservedProperty::WriteAvroCompressionLevel,
+        ReservedProperty::WriteOrcStripeSizeBytes,
+        ReservedProperty::WriteOrcBlockSizeBytes,
+        ReservedProperty::WriteOrcCompressionCodec,
+        ReservedProperty::WriteOrcCompressionStrategy,
+        ReservedProperty::WriteOrcBloomFilterColumns,
+        ReservedProperty::WriteOrcBloomFilterFpp,
+        ReservedProperty::WriteLocationProviderImpl,
+        ReservedProperty::WriteMetadataCompressionCodec,
+        ReservedProperty::WriteMetadataMetricsDefault,
+        ReservedProperty::WriteMetadataMetricsMaxInferredColumnDefaults,
+        ReservedProperty::WriteTargetFileSizeBytes,
+        ReservedProperty::WriteDistributionMode,
+        ReservedProperty::WriteWapEnabled,
+        ReservedProperty::WriteSummaryPartitionLimit,
+        ReservedProperty::WriteMetadataDeleteAfterCommitEnabled,
+        ReservedProperty::WriteMetadataPreviousVersionsMax,
+        ReservedProperty::WriteObjectStorageEnabled,
+        ReservedProperty::WriteDataPath,
+        ReservedProperty::WriteMetadataPath,
+        ReservedProperty::WriteDeleteMode,
+        ReservedProperty::WriteUpdateMode,
+        ReservedProperty::WriteMergeMode,
+        ReservedProperty::CommitRetryNumRetries,
+        ReservedProperty::CommitRetryMinWaitMs,
+        ReservedProperty::CommitRetryMaxWaitMs,
+        ReservedProperty::CommitRetryTotalTimeoutMs,
+        ReservedProperty::CommitStatusCheckNumRetries,
+        ReservedProperty::CommitStatusCheckMinWaitMs,
+        ReservedProperty::CommitStatusCheckMaxWaitMs,
+        ReservedProperty::CommitStatusCheckTotalTimeoutMs,
+        ReservedProperty::CommitManifestTargetSizeBytes,
+        ReservedProperty::CommitManifestMinCountToMerge,
+        ReservedProperty::CommitManifestMergeEnabled,
+        ReservedProperty::HistoryExpireMaxSnapshotAgeMs,
+        ReservedProperty::HistoryExpireMinSnapshotsToKeep,
+        ReservedProperty::HistoryExpireMaxRefAgeMs,
+        ReservedProperty::GcEnabled,
+    ];
+
+    /// The property key as it appears in the table properties map.
+    pub fn as_str(&self) -> &'static str {
+        use ReservedProperty::*;
+        match self {
+            ReadSplitTargetSize => "read.split.target-size",
+            ReadSplitMetadataTargetSize => "read.split.metadata-target-size",
+            ReadSplitPlanningLookback => "read.split.planning-lookback",
+            ReadSplitOpenFileCost => "read.split.open-file-cost",
+            ReadParquetVectorizationEnabled => "read.parquet.vectorization.enabled",
+            ReadParquetVectorizationBatchSize => "read.parquet.vectorization.batch-size",
+            ReadOrcVectorizationEnabled => "read.orc.vectorization.enabled",
+            ReadOrcVectorizationBatchSize => "read.orc.vectorization.batch-size",
+            WriteFormatDefault => "write.format.default",
+            WriteDeleteFormatDefault => "write.delete.format.default",
+            WriteParquetRowGroupSizeBytes => "write.parquet.row-group-size-bytes",
+            WriteParquetPageSizeBytes => "write.parquet.page-size-bytes",
+            WriteParquetDictSizeBytes => "write.parquet.dict-size-bytes",
+            WriteParquetCompressionCodec => "write.parquet.compression-codec",
+            WriteParquetCompressionLevel => "write.parquet.compression-level",
+            WriteParquetBloomFilterMaxBytes => "write.parquet.bloom-filter-max-bytes",
+            WriteAvroCompressionCodec => "write.avro.compression-codec",
+            WriteAvroCompressionLevel => "write.avro.compression-level",
+            WriteOrcStripeSizeBytes => "write.orc.stripe-size-bytes",
+            WriteOrcBlockSizeBytes => "write.orc.block-size-bytes",
+            WriteOrcCompressionCodec => "write.orc.compression-codec",
+            WriteOrcCompressionStrategy => "write.orc.compression-strategy",
+            WriteOrcBloomFilterColumns => "write.orc.bloom.filter.columns",
+            WriteOrcBloomFilterFpp => "write.orc.bloom.filter.fpp",
+            WriteLocationProviderImpl => "write.location-provider.impl",
+            WriteMetadataCompressionCodec => "write.metadata.compression-codec",
+            WriteMetadataMetricsDefault => "write.metadata.metrics.default",
+            WriteMetadataMetricsMaxInferredColumnDefaults => {
+                "write.metadata.metrics.max-inferred-column-defaults"
+            }
+            WriteTargetFileSizeBytes => "write.target-file-size-bytes",
+            WriteDistributionMode => "write.distribution-mode",
+            WriteWapEnabled => "write.wap.enabled",
+            WriteSummaryPartitionLimit => "write.summary.partition-limit",
+            WriteMetadataDeleteAfterCommitEnabled => "write.metadata.delete-after-commit.enabled",
+            WriteMetadataPreviousVersionsMax => "write.metadata.previous-versions-max",
+            WriteObjectStorageEnabled => "write.object-storage.enabled",
+            WriteDataPath => "write.data.path",
+            WriteMetadataPath => "write.metadata.path",
+            WriteDeleteMode => "write.delete.mode",
+            WriteUpdateMode => "write.update.mode",
+            WriteMergeMode => "write.merge.mode",
+            CommitRetryNumRetries => "commit.retry.num-retries",
+            CommitRetryMinWaitMs => "commit.retry.min-wait-ms",
+            CommitRetryMaxWaitMs => "commit.retry.max-wait-ms",
+            CommitRetryTotalTimeoutMs => "commit.retry.total-timeout-ms",
+            CommitStatusCheckNumRetries => "commit.status-check.num-retries",
+            CommitStatusCheckMinWaitMs => "commit.status-check.min-wait-ms",
+            CommitStatusCheckMaxWaitMs => "commit.status-check.max-wait-ms",
+            CommitStatusCheckTotalTimeoutMs => "commit.status-check.total-timeout-ms",
+            CommitManifestTargetSizeBytes => "commit.manifest.target-size-bytes",
+            CommitManifestMinCountToMerge => "commit.manifest.min-count-to-merge",
+            CommitManifestMergeEnabled => "commit.manifest-merge.enabled",
+            HistoryExpireMaxSnapshotAgeMs => "history.expire.max-snapshot-age-ms",
+            HistoryExpireMinSnapshotsToKeep => "history.expire.min-snapshots-to-keep",
+            HistoryExpireMaxRefAgeMs => "history.expire.max-ref-age-ms",
+            GcEnabled => "gc.enabled",
+        }
+    }
+}
+
+impl FromStr for ReservedProperty {
+    type Err = IcebergError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReservedProperty::ALL
+            .iter()
+            .find(|property| property.as_str() == s)
+            .copied()
+            .ok_or_else(|| IcebergError::Message(format!("Unknown reserved property {s}")))
+    }
+}
+
+/// Serialize for ReservedProperty as its property key.
+impl Serialize for ReservedProperty {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserialize for ReservedProperty from its property key.
+impl<'de> Deserialize<'de> for ReservedProperty {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        ReservedProperty::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{
+        collections::{HashMap, HashSet},
+        str::FromStr,
+    };
+
     use anyhow::Result;
+    use serde_json::json;
 
-    use super::TableMetadataV2;
+    use super::{MetadataParseOptions, ReservedProperty, TableMetadataV2, FORMAT_VERSION_V2};
+    use crate::error::IcebergError;
 
     #[test]
     fn test_deserialize_table_data_v2() -> Result<()> {
@@ -158,10 +698,12 @@ mod tests {
                 "default-sort-order-id": 0
             }
         "#;
-        let metadata = serde_json::from_str::<TableMetadataV2>(&data)?;
+        let metadata = serde_json::from_str::<TableMetadataV2>(data)?;
+        assert_eq!(metadata, TableMetadataV2::from_str(data)?);
         //test serialise deserialise works.
-        let metadata_two: TableMetadataV2 =
-            serde_json::from_str(&serde_json::to_string(&metadata)?)?;
+        let json = serde_json::to_value(&metadata)?;
+        assert_eq!(FORMAT_VERSION_V2, json["format-version"]);
+        let metadata_two: TableMetadataV2 = serde_json::from_value(json)?;
         assert_eq!(metadata, metadata_two);
 
         Ok(())
@@ -175,7 +717,7 @@ mod tests {
                 "table-uuid": "xxxx"
             }
         "#;
-        assert!(serde_json::from_str::<TableMetadataV2>(&data).is_err());
+        assert!(serde_json::from_str::<TableMetadataV2>(data).is_err());
         Ok(())
     }
     #[test]
@@ -185,7 +727,511 @@ mod tests {
                 "format-version" : 1
             }
         "#;
-        assert!(serde_json::from_str::<TableMetadataV2>(&data).is_err());
+        assert!(serde_json::from_str::<TableMetadataV2>(data).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserved_property() -> Result<()> {
+        let property = ReservedProperty::from_str("commit.retry.num-retries")?;
+        assert_eq!(ReservedProperty::CommitRetryNumRetries, property);
+        assert_eq!("commit.retry.num-retries", property.as_str());
+        assert_eq!(
+            ReservedProperty::WriteMetadataPreviousVersionsMax,
+            ReservedProperty::from_str(
+                ReservedProperty::WriteMetadataPreviousVersionsMax.as_str()
+            )?
+        );
+        assert!(matches!(
+            ReservedProperty::from_str("commit.retry.num-retrys"),
+            Err(IcebergError::Message(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserved_property_round_trip() -> Result<()> {
+        let mut keys = HashSet::new();
+        for property in ReservedProperty::ALL {
+            assert!(keys.insert(property.as_str()));
+            assert_eq!(*property, ReservedProperty::from_str(property.as_str())?);
+            let json = serde_json::to_string(property)?;
+            assert_eq!(*property, serde_json::from_str::<ReservedProperty>(&json)?);
+        }
+        assert_eq!(
+            ReservedProperty::GcEnabled,
+            ReservedProperty::from_str("gc.enabled")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserved_property_serde() -> Result<()> {
+        let property = ReservedProperty::WriteMetadataDeleteAfterCommitEnabled;
+        let json = serde_json::to_string(&property)?;
+        assert_eq!(r#""write.metadata.delete-after-commit.enabled""#, json);
+        assert_eq!(property, serde_json::from_str(&json)?);
+
+        let properties: HashMap<ReservedProperty, String> =
+            serde_json::from_str(r#"{"commit.retry.num-retries": "1"}"#)?;
+        assert_eq!(
+            Some(&"1".to_string()),
+            properties.get(&ReservedProperty::CommitRetryNumRetries)
+        );
+        assert!(serde_json::from_str::<ReservedProperty>(r#""commit.retry.num-retrys""#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_reserved_property_covers_fixture() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 2,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 1,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 1,
+                "schemas": [],
+                "current-schema-id" : 1,
+                "partition-specs": [],
+                "default-spec-id": 1,
+                "last-partition-id": 1,
+                "properties": {
+                    "commit.retry.num-retries": "1"
+                },
+                "sort-orders": [],
+                "default-sort-order-id": 0
+            }
+        "#;
+        let metadata = serde_json::from_str::<TableMetadataV2>(data)?;
+        for key in metadata.properties.as_ref().unwrap().keys() {
+            assert!(ReservedProperty::from_str(key).is_ok());
+        }
+        assert_eq!(
+            Some("1"),
+            metadata.property(ReservedProperty::CommitRetryNumRetries)
+        );
+        assert_eq!(
+            None,
+            metadata.property(ReservedProperty::WriteFormatDefault)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_schema_by_id() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 2,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 1,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 2,
+                "schemas": [
+                    {
+                        "schema-id" : 0,
+                        "type" : "struct",
+                        "fields" :[
+                            {
+                                "id": 1,
+                                "name": "id",
+                                "required": true,
+                                "field_type": "long"
+                            }
+                        ]
+                    },
+                    {
+                        "schema-id" : 1,
+                        "type" : "struct",
+                        "fields" :[
+                            {
+                                "id": 1,
+                                "name": "id",
+                                "required": true,
+                                "field_type": "long"
+                            },
+                            {
+                                "id": 2,
+                                "name": "data",
+                                "required": false,
+                                "field_type": "string"
+                            }
+                        ]
+                    }
+                ],
+                "current-schema-id" : 1,
+                "partition-specs": [],
+                "default-spec-id": 0,
+                "last-partition-id": 0,
+                "sort-orders": [],
+                "default-sort-order-id": 0
+            }
+        "#;
+        let metadata = serde_json::from_str::<TableMetadataV2>(data)?;
+        let current = metadata.schema_by_id(metadata.current_schema_id).unwrap();
+        assert_eq!(1, current.schema_id);
+        assert_eq!(2, current.struct_fields.fields.len());
+        let previous = metadata.schema_by_id(0).unwrap();
+        assert_eq!(0, previous.schema_id);
+        assert_eq!(1, previous.struct_fields.fields.len());
+        assert!(metadata.schema_by_id(2).is_none());
+        assert_eq!(1, metadata.current_schema()?.schema_id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_lenient_parsing() -> Result<()> {
+        let valid = json!({
+            "format-version" : 2,
+            "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+            "location": "s3://b/wh/data.db/table",
+            "last-sequence-number" : 1,
+            "last-updated-ms": 1515100955770_i64,
+            "last-column-id": 1,
+            "schemas": [],
+            "current-schema-id" : 1,
+            "partition-specs": [],
+            "default-spec-id": 0,
+            "last-partition-id": 0,
+            "sort-orders": [],
+            "default-sort-order-id": 0
+        });
+        let strict = MetadataParseOptions { lenient: false };
+        let lenient = MetadataParseOptions { lenient: true };
+        assert!(TableMetadataV2::from_str_with(&valid.to_string(), strict).is_ok());
+
+        // current-snapshot-id of -1 for no snapshot
+        let mut sentinel = valid.clone();
+        sentinel["current-snapshot-id"] = json!(-1);
+        assert!(matches!(
+            TableMetadataV2::from_str(&sentinel.to_string()),
+            Err(IcebergError::InvalidMetadata(_))
+        ));
+        assert!(serde_json::from_value::<TableMetadataV2>(sentinel.clone()).is_err());
+        let metadata = TableMetadataV2::from_str_with(&sentinel.to_string(), lenient)?;
+        assert_eq!(None, metadata.current_snapshot_id);
+
+        // missing last-sequence-number
+        let mut no_sequence_number = valid.clone();
+        no_sequence_number
+            .as_object_mut()
+            .unwrap()
+            .remove("last-sequence-number");
+        assert!(matches!(
+            TableMetadataV2::from_str_with(&no_sequence_number.to_string(), strict),
+            Err(IcebergError::Json(_))
+        ));
+        let metadata = TableMetadataV2::from_str_with(&no_sequence_number.to_string(), lenient)?;
+        assert_eq!(0, metadata.last_sequence_number);
+
+        // missing sort-orders and default-sort-order-id
+        let mut no_sort_orders = valid;
+        let fields = no_sort_orders.as_object_mut().unwrap();
+        fields.remove("sort-orders");
+        fields.remove("default-sort-order-id");
+        assert!(matches!(
+            TableMetadataV2::from_str_with(&no_sort_orders.to_string(), strict),
+            Err(IcebergError::Json(_))
+        ));
+        let metadata = TableMetadataV2::from_str_with(&no_sort_orders.to_string(), lenient)?;
+        assert!(metadata.sort_orders.is_empty());
+        assert_eq!(0, metadata.default_sort_order_id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_format_version() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 3,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 1,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 1,
+                "schemas": [],
+                "current-schema-id" : 1,
+                "partition-specs": [],
+                "default-spec-id": 0,
+                "last-partition-id": 0,
+                "sort-orders": [],
+                "default-sort-order-id": 0
+            }
+        "#;
+        assert!(matches!(
+            TableMetadataV2::from_str(data),
+            Err(IcebergError::UnsupportedFormatVersion(3))
+        ));
+        assert!(serde_json::from_str::<TableMetadataV2>(data).is_err());
+        assert!(
+            serde_json::from_str::<TableMetadataV2>(&data.replace("3,", "2,"))?
+                .sort_orders
+                .is_empty()
+        );
+        assert!(serde_json::from_str::<TableMetadataV2>(
+            &data.replace(r#""format-version" : 3,"#, "")
+        )
+        .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_snapshot_refs() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 2,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 2,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 1,
+                "schemas": [],
+                "current-schema-id" : 1,
+                "partition-specs": [],
+                "default-spec-id": 0,
+                "last-partition-id": 0,
+                "sort-orders": [],
+                "default-sort-order-id": 0,
+                "current-snapshot-id": 1,
+                "snapshots": [
+                    {
+                        "snapshot-id": 1,
+                        "sequence-number": 1,
+                        "timestamp-ms": 1515100955770,
+                        "summary": { "operation": "append" },
+                        "manifest-list": "s3://b/wh/.../s1.avro"
+                    },
+                    {
+                        "snapshot-id": 2,
+                        "parent-snapshot-id": 1,
+                        "sequence-number": 2,
+                        "timestamp-ms": 1515100955771,
+                        "summary": { "operation": "append" },
+                        "manifest-list": "s3://b/wh/.../s2.avro"
+                    }
+                ],
+                "refs": {
+                    "main": { "snapshot-id": 2, "type": "branch", "min-snapshots-to-keep": 1, "max-snapshot-age-ms": 1, "max-ref-age-ms": 1 },
+                    "v1": { "snapshot-id": 1, "type": "tag", "max-ref-age-ms": 1 },
+                    "dangling": { "snapshot-id": 3, "type": "tag", "max-ref-age-ms": 1 }
+                }
+            }
+        "#;
+        let mut metadata = TableMetadataV2::from_str(data)?;
+        assert_eq!(2, metadata.snapshot_for_ref("main").unwrap().snapshot_id);
+        assert_eq!(1, metadata.snapshot_for_ref("v1").unwrap().snapshot_id);
+        assert!(metadata.snapshot_for_ref("dangling").is_none());
+        assert!(metadata.snapshot_for_ref("missing").is_none());
+
+        // the main branch wins over a stale current-snapshot-id
+        assert_eq!(2, metadata.current_snapshot().unwrap().snapshot_id);
+        assert!(metadata.validate().is_err());
+        // without a main branch current-snapshot-id is used
+        metadata.refs.as_mut().unwrap().remove("main");
+        assert_eq!(1, metadata.current_snapshot().unwrap().snapshot_id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_current_schema_and_default_spec() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 2,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 1,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 1,
+                "schemas": [
+                    {
+                        "schema-id" : 0,
+                        "type" : "struct",
+                        "fields" : []
+                    }
+                ],
+                "current-schema-id" : 1,
+                "partition-specs": [
+                    {
+                        "spec-id": 0,
+                        "fields": []
+                    }
+                ],
+                "default-spec-id": 1,
+                "last-partition-id": 0,
+                "sort-orders": [],
+                "default-sort-order-id": 0
+            }
+        "#;
+        let mut metadata = TableMetadataV2::from_str(data)?;
+        assert!(matches!(
+            metadata.current_schema(),
+            Err(IcebergError::InvalidMetadata(_))
+        ));
+        assert!(matches!(
+            metadata.default_spec(),
+            Err(IcebergError::InvalidMetadata(_))
+        ));
+
+        metadata.current_schema_id = 0;
+        metadata.default_spec_id = 0;
+        assert_eq!(0, metadata.current_schema()?.schema_id);
+        assert_eq!(0, metadata.default_spec()?.spec_id);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate() -> Result<()> {
+        let data = r#"
+            {
+                "format-version" : 2,
+                "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+                "location": "s3://b/wh/data.db/table",
+                "last-sequence-number" : 1,
+                "last-updated-ms": 1515100955770,
+                "last-column-id": 1,
+                "schemas": [
+                    {
+                        "schema-id" : 1,
+                        "type" : "struct",
+                        "fields" : []
+                    }
+                ],
+                "current-schema-id" : 1,
+                "partition-specs": [
+                    {
+                        "spec-id": 1,
+                        "fields": []
+                    }
+                ],
+                "default-spec-id": 1,
+                "last-partition-id": 0,
+                "sort-orders": [
+                    {
+                        "order-id": 1,
+                        "fields": []
+                    }
+                ],
+                "default-sort-order-id": 1,
+                "current-snapshot-id": 1,
+                "snapshots": [
+                    {
+                        "snapshot-id": 1,
+                        "sequence-number": 1,
+                        "timestamp-ms": 1515100955770,
+                        "summary": { "operation": "append" },
+                        "manifest-list": "s3://b/wh/.../s1.avro"
+                    }
+                ],
+                "refs": {
+                    "main": { "snapshot-id": 1, "type": "branch", "min-snapshots-to-keep": 1, "max-snapshot-age-ms": 1, "max-ref-age-ms": 1 },
+                    "v1": { "snapshot-id": 1, "type": "tag", "max-ref-age-ms": 1 }
+                }
+            }
+        "#;
+        let is_invalid = |metadata: &TableMetadataV2| {
+            matches!(metadata.validate(), Err(IcebergError::InvalidMetadata(_)))
+        };
+        let metadata = TableMetadataV2::from_str(data)?;
+        assert!(metadata.validate().is_ok());
+
+        let mut unsorted = TableMetadataV2::from_str(data)?;
+        unsorted.default_sort_order_id = 0;
+        assert!(unsorted.validate().is_ok());
+
+        let mut no_refs = TableMetadataV2::from_str(data)?;
+        no_refs.refs = None;
+        assert!(no_refs.validate().is_ok());
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.schemas[0]
+            .struct_fields
+            .fields
+            .push(serde_json::from_value(json!({
+                "id": 1,
+                "name": "point",
+                "required": false,
+                "field_type": {
+                    "type": "struct",
+                    "fields": [
+                        {
+                            "id": 2,
+                            "name": "x",
+                            "required": true,
+                            "field_type": "int",
+                            "write-default": "a"
+                        }
+                    ]
+                }
+            }))?);
+        assert!(matches!(invalid.validate(), Err(IcebergError::Schema(_))));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.current_schema_id = 2;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.default_spec_id = 2;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.default_sort_order_id = 2;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.current_snapshot_id = Some(2);
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid
+            .refs
+            .as_mut()
+            .unwrap()
+            .get_mut("v1")
+            .unwrap()
+            .snapshot_id = 2;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid
+            .refs
+            .as_mut()
+            .unwrap()
+            .get_mut("main")
+            .unwrap()
+            .retention = serde_json::from_value(json!({ "type": "tag", "max-ref-age-ms": 1 }))?;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid.current_snapshot_id = None;
+        assert!(is_invalid(&invalid));
+
+        let mut invalid = TableMetadataV2::from_str(data)?;
+        invalid
+            .snapshots
+            .as_mut()
+            .unwrap()
+            .push(serde_json::from_str(
+                r#"
+            {
+                "snapshot-id": 2,
+                "parent-snapshot-id": 1,
+                "sequence-number": 2,
+                "timestamp-ms": 1515100955771,
+                "summary": { "operation": "append" },
+                "manifest-list": "s3://b/wh/.../s2.avro"
+            }
+            "#,
+            )?);
+        invalid
+            .refs
+            .as_mut()
+            .unwrap()
+            .get_mut("main")
+            .unwrap()
+            .snapshot_id = 2;
+        assert!(is_invalid(&invalid));
         Ok(())
     }
 }
@@ -0,0 +1,148 @@
+/*!
+Helpers for working with table and file locations.
+
+[parse_location] splits a location such as `s3://bucket/path/to/file` into its
+[Scheme], bucket (or container) and key.
+*/
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use crate::error::IcebergError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+/// The storage schemes a location can use.
+pub enum Scheme {
+    /// Amazon S3, `s3://`
+    S3,
+    /// Amazon S3 through the Hadoop S3A connector, `s3a://`
+    S3a,
+    /// Google Cloud Storage, `gs://`
+    Gcs,
+    /// Azure Data Lake Storage Gen2, `abfs://`
+    Abfs,
+    /// Azure Data Lake Storage Gen2 over TLS, `abfss://`
+    Abfss,
+    /// The local file system, `file://`
+    File,
+}
+
+/// Split a location into its scheme, bucket and key.
+///
+/// For Azure locations of the form `abfss://container@account.dfs.core.windows.net/key`
+/// the container is returned as the bucket, and Azure locations without a container
+/// are an error. For `file://` locations the bucket is empty and the key is the
+/// absolute path.
+pub fn parse_location(location: &str) -> Result<(Scheme, String, String), IcebergError> {
+    lazy_static! {
+        static ref RE: Regex =
+            Regex::new(r#"^(?P<scheme>[a-z0-9]+)://(?P<authority>[^/]*)(?P<path>/.*)?$"#).unwrap();
+    }
+    let err_msg = || IcebergError::Message(format!("Invalid location {location}"));
+
+    let caps = RE.captures(location).ok_or_else(err_msg)?;
+    let scheme = match caps.name("scheme").map(|s| s.as_str()) {
+        Some("s3") => Scheme::S3,
+        Some("s3a") => Scheme::S3a,
+        Some("gs") => Scheme::Gcs,
+        Some("abfs") => Scheme::Abfs,
+        Some("abfss") => Scheme::Abfss,
+        Some("file") => Scheme::File,
+        _ => {
+            return Err(IcebergError::Message(format!(
+                "Unsupported scheme in location {location}"
+            )))
+        }
+    };
+    let authority = caps.name("authority").map(|a| a.as_str()).unwrap_or("");
+    let path = caps.name("path").map(|p| p.as_str()).unwrap_or("");
+
+    match scheme {
+        Scheme::File if authority.is_empty() => Ok((scheme, String::new(), path.to_string())),
+        Scheme::File => Err(err_msg()),
+        _ if authority.is_empty() => Err(err_msg()),
+        Scheme::Abfs | Scheme::Abfss => match authority.split_once('@') {
+            Some((container, _)) if !container.is_empty() => Ok((
+                scheme,
+                container.to_string(),
+                path.trim_start_matches('/').to_string(),
+            )),
+            _ => Err(err_msg()),
+        },
+        _ => Ok((
+            scheme,
+            authority.to_string(),
+            path.trim_start_matches('/').to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        let locations = vec![
+            (
+                "s3://bucket/wh/data.db/table",
+                Scheme::S3,
+                "bucket",
+                "wh/data.db/table",
+            ),
+            (
+                "s3a://bucket/wh/data.db/table",
+                Scheme::S3a,
+                "bucket",
+                "wh/data.db/table",
+            ),
+            (
+                "gs://bucket/wh/data.db/table",
+                Scheme::Gcs,
+                "bucket",
+                "wh/data.db/table",
+            ),
+            (
+                "abfs://container@account.dfs.core.windows.net/wh/table",
+                Scheme::Abfs,
+                "container",
+                "wh/table",
+            ),
+            (
+                "abfss://container@account.dfs.core.windows.net/wh/table",
+                Scheme::Abfss,
+                "container",
+                "wh/table",
+            ),
+            ("file:///tmp/wh/table", Scheme::File, "", "/tmp/wh/table"),
+            ("s3://bucket", Scheme::S3, "bucket", ""),
+        ];
+        for (location, scheme, bucket, key) in locations {
+            assert_eq!(
+                (scheme, bucket.to_string(), key.to_string()),
+                parse_location(location).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_location_bucket_in_key() {
+        assert_eq!(
+            (
+                Scheme::S3,
+                "bucket".to_string(),
+                "bucket/data/bucket.parquet".to_string()
+            ),
+            parse_location("s3://bucket/bucket/data/bucket.parquet").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_location() {
+        assert!(parse_location("wh/data.db/table").is_err());
+        assert!(parse_location("hdfs://namenode/wh/table").is_err());
+        assert!(parse_location("s3:///wh/table").is_err());
+        assert!(parse_location("file://host/tmp/table").is_err());
+        assert!(parse_location("abfss://account.dfs.core.windows.net/wh/table").is_err());
+        assert!(parse_location("abfs://@account.dfs.core.windows.net/wh/table").is_err());
+    }
+}